@@ -1,5 +1,6 @@
 use crate::Error;
-use std::mem::size_of;
+use alloc::vec::Vec;
+use core::task::Poll;
 
 /// Ensure the step size is valid
 /// Step must be [1, 8] and a factor of 8
@@ -16,6 +17,72 @@ fn bytes_per_byte(step: usize) -> usize {
     u8::BITS as usize / step
 }
 
+/// Builds the 256-entry spread table for `step`: `table[byte][lane]` is the low `step` bits
+/// that lane `lane` of an encoded byte must OR in to embed `byte`
+const fn build_spread_table(step: usize) -> [[u8; 8]; 256] {
+    let space = 8 / step;
+    let mask = (1u16 << step) - 1;
+    let mut table = [[0u8; 8]; 256];
+    let mut byte = 0usize;
+    while byte < 256 {
+        let mut lane = 0usize;
+        while lane < space {
+            table[byte][lane] = ((byte >> (lane * step)) as u16 & mask) as u8;
+            lane += 1;
+        }
+        byte += 1;
+    }
+    table
+}
+
+/// Builds the 256-entry gather table for `step`: `table[slot][lane]` is the contribution of a
+/// slot with raw value `slot` to the decoded byte, already shifted into lane `lane`'s position
+const fn build_gather_table(step: usize) -> [[u8; 8]; 256] {
+    let space = 8 / step;
+    let mask = (1u16 << step) - 1;
+    let mut table = [[0u8; 8]; 256];
+    let mut slot = 0usize;
+    while slot < 256 {
+        let mut lane = 0usize;
+        while lane < space {
+            table[slot][lane] = (((slot as u16 & mask) << (lane * step)) & 0xff) as u8;
+            lane += 1;
+        }
+        slot += 1;
+    }
+    table
+}
+
+const SPREAD_1: [[u8; 8]; 256] = build_spread_table(1);
+const SPREAD_2: [[u8; 8]; 256] = build_spread_table(2);
+const SPREAD_4: [[u8; 8]; 256] = build_spread_table(4);
+const SPREAD_8: [[u8; 8]; 256] = build_spread_table(8);
+
+const GATHER_1: [[u8; 8]; 256] = build_gather_table(1);
+const GATHER_2: [[u8; 8]; 256] = build_gather_table(2);
+const GATHER_4: [[u8; 8]; 256] = build_gather_table(4);
+const GATHER_8: [[u8; 8]; 256] = build_gather_table(8);
+
+fn spread_table(step: usize) -> &'static [[u8; 8]; 256] {
+    match step {
+        1 => &SPREAD_1,
+        2 => &SPREAD_2,
+        4 => &SPREAD_4,
+        8 => &SPREAD_8,
+        _ => unreachable!(),
+    }
+}
+
+fn gather_table(step: usize) -> &'static [[u8; 8]; 256] {
+    match step {
+        1 => &GATHER_1,
+        2 => &GATHER_2,
+        4 => &GATHER_4,
+        8 => &GATHER_8,
+        _ => unreachable!(),
+    }
+}
+
 /// Encodes `data` into the `buffer` using the `step` least significant bits
 ///
 /// # Arguments
@@ -35,16 +102,13 @@ unsafe fn encode_raw_unsafe<'a>(buffer: &'a mut [u8], data: &[u8], step: usize)
         buffer.len() >= (data.len() * space),
         "Buffer is too small to encode the data"
     );
+    let clear_mask = !(((1u16 << step) - 1) as u8);
+    let table = spread_table(step);
     let mut it = buffer.iter_mut();
     for byte_in in data {
-        let mut bit_read: u8 = 0;
-        for slot in (&mut it).take(space) {
-            for bit_write in 0..step {
-                let insert = (byte_in & (1 << bit_read)) >> bit_read;
-                let old = (*slot) & (!(1 << bit_write));
-                *slot = old | (insert << bit_write);
-                bit_read += 1;
-            }
+        let lanes = &table[*byte_in as usize];
+        for (lane, slot) in (&mut it).take(space).enumerate() {
+            *slot = (*slot & clear_mask) | lanes[lane];
         }
     }
     &mut buffer[data.len() * space..]
@@ -58,20 +122,15 @@ unsafe fn encode_raw_unsafe<'a>(buffer: &'a mut [u8], data: &[u8], step: usize)
 /// The decoded byte or `None` if the buffer is too small
 fn decode_byte(buffer: &[u8], step: usize) -> Option<u8> {
     debug_assert_step_size!(step);
-    let mut current: u8 = 0;
-    let mut bit = 0;
-    for slot in buffer {
-        for bit_read in 0..step {
-            current <<= 1;
-            current |= (*slot & (1 << bit_read)) >> bit_read;
-            bit += 1;
-        }
+    if buffer.len() * step != u8::BITS as usize {
+        return None;
     }
-    if bit == u8::BITS as u8 {
-        Some(current.reverse_bits())
-    } else {
-        None
+    let table = gather_table(step);
+    let mut current: u8 = 0;
+    for (lane, slot) in buffer.iter().enumerate() {
+        current |= table[*slot as usize][lane];
     }
+    Some(current)
 }
 
 /// Encodes `data` into the `buffer` using the `step` least significant bits
@@ -116,59 +175,563 @@ pub fn encode_raw<'a>(
 /// # Ok(())
 /// # }
 /// ```
-pub fn decode_raw<'a>(buffer: &'a [u8], size: usize, step: usize) -> (&'a [u8], Vec<u8>) {
+/// A `buffer` shorter than `size * bytes_per_byte(step)` is not a bug: it just yields fewer
+/// than `size` bytes, so short or truncated cover buffers can be handled without panicking.
+pub fn decode_raw(buffer: &[u8], size: usize, step: usize) -> (&[u8], Vec<u8>) {
     debug_assert_step_size!(step);
     let bpb = bytes_per_byte(step);
-    debug_assert!(
-        buffer.len() >= size * bpb,
-        "Buffer is too small to hold the requested amount of data"
-    );
-    let mut out = Vec::<u8>::new();
-    for index in (0..buffer.len()).step_by(bpb).take(size) {
+    let count = size.min(buffer.len() / bpb);
+    let mut out = Vec::with_capacity(count);
+    for index in (0..count * bpb).step_by(bpb) {
         if let Some(byte) = decode_byte(&buffer[index..index + bpb], step) {
             out.push(byte);
         } else {
             break;
         }
     }
-    (&buffer[(size * bpb)..], out)
+    let consumed = out.len() * bpb;
+    (&buffer[consumed..], out)
+}
+
+/// Maximum number of bytes an unsigned LEB128-encoded `u64` can occupy
+const LEB128_MAX_BYTES: usize = 10;
+
+/// Encodes `value` as an unsigned LEB128 varint
+///
+/// Emits the low 7 bits of `value` per byte, setting the high bit on every byte but the
+/// last to signal that more bytes follow. A `value` of `0` still emits a single `0x00` byte.
+fn leb128_encode(mut value: u64) -> Vec<u8> {
+    let mut out = Vec::new();
+    loop {
+        let mut byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value != 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if value == 0 {
+            return out;
+        }
+    }
+}
+
+/// Decodes an unsigned LEB128 varint from `buffer`, reading one embedded byte at a time
+/// # Returns
+/// The unused portion of the `buffer` and the decoded value, or `None` if the buffer ran
+/// out, or [`LEB128_MAX_BYTES`] bytes were read, before a terminating byte was found
+fn leb128_decode(mut buffer: &[u8], step: usize) -> Option<(&[u8], u64)> {
+    let mut value: u64 = 0;
+    for index in 0..LEB128_MAX_BYTES {
+        let (rest, byte) = decode_raw(buffer, 1, step);
+        let byte = *byte.first()?;
+        buffer = rest;
+        value |= ((byte & 0x7f) as u64) << (index * 7);
+        if byte & 0x80 == 0 {
+            return Some((buffer, value));
+        }
+    }
+    None
+}
+
+/// Magic marker written ahead of the length header, letting [`decode`] tell an embedded
+/// payload apart from an image with nothing hidden in it
+const MAGIC: [u8; 4] = *b"STEG";
+
+/// Precomputed CRC-32 (IEEE 802.3) lookup table, used to checksum payloads in [`encode`]/[`decode`]
+const CRC32_TABLE: [u32; 256] = build_crc32_table();
+
+const fn build_crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut byte = 0usize;
+    while byte < 256 {
+        let mut crc = byte as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[byte] = crc;
+        byte += 1;
+    }
+    table
+}
+
+/// Computes the CRC-32 (IEEE 802.3) checksum of `data`
+///
+/// Hand-rolled against a precomputed table rather than pulled in from `crc32fast`, so that
+/// [`encode`]/[`decode`] — the one format with a verified container, see their doc comments —
+/// stay usable under `no_std`.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for byte in data {
+        let index = ((crc ^ (*byte as u32)) & 0xff) as usize;
+        crc = (crc >> 8) ^ CRC32_TABLE[index];
+    }
+    !crc
 }
 
+/// Encodes `data` into the `buffer` using the `step` least significant bits
+///
+/// Ahead of the payload this writes a magic marker and the payload's length as a LEB128
+/// varint (so messages under 128 bytes only spend a single header byte on the length instead
+/// of `size_of::<usize>()`), and appends a CRC-32 trailer so [`decode`] can tell an embedded
+/// payload apart from an image with nothing hidden in it and detect corruption.
+///
+/// `encode`/[`decode`] are the only verified container in this module: the magic marker and
+/// CRC-32 trailer are not part of [`encode_compressed`]/[`decode_compressed`], [`Encoder`]/
+/// [`Decoder`], or [`encode_buf`]/[`decode_buf`], which each use their own bare
+/// `leb128(len) + payload` framing (plus a codec tag byte, for the compressed variant) with no
+/// "is there even a payload here" signal and no corruption detection.
 pub fn encode<'a>(buffer: &'a mut [u8], data: &[u8], step: usize) -> Result<&'a mut [u8], Error> {
     debug_assert_step_size!(step);
-    let size = data.len().to_be_bytes();
-    let buffer = encode_raw(buffer, &size, step)?;
-    Ok(encode_raw(buffer, data, step)?)
+    let mut header = Vec::from(MAGIC);
+    header.extend(leb128_encode(data.len() as u64));
+    let buffer = encode_raw(buffer, &header, step)?;
+    let buffer = encode_raw(buffer, data, step)?;
+    encode_raw(buffer, &crc32(data).to_be_bytes(), step)
 }
 
+/// Decodes a payload previously written by [`encode`]
+/// # Errors
+/// Returns [`Error::NoPayload`] if `buffer` does not start with the magic marker, and
+/// [`Error::ChecksumMismatch`] if the recovered payload's CRC-32 does not match the trailer.
 pub fn decode(buffer: &[u8], step: usize) -> Result<Vec<u8>, Error> {
     debug_assert_step_size!(step);
-    let (buffer, size) = decode_raw(buffer, size_of::<usize>(), step);
-    if size.len() != size_of::<usize>() {
-        return Err(Error::BufferTooSmall {
-            actual: size.len(),
-            required: size_of::<usize>(),
-        });
+    let (buffer, magic) = decode_raw(buffer, MAGIC.len(), step);
+    if magic.as_slice() != MAGIC {
+        return Err(Error::NoPayload);
     }
-    let size = usize::from_be_bytes(size.try_into().unwrap());
-    let (_, data) = decode_raw(buffer, size, step);
+
+    let (buffer, size) = leb128_decode(buffer, step).ok_or(Error::BufferTooSmall {
+        actual: buffer.len(),
+        required: 1,
+    })?;
+    let size = size as usize;
+    let (buffer, data) = decode_raw(buffer, size, step);
     if data.len() != size {
-        Err(Error::BufferTooSmall {
+        return Err(Error::BufferTooSmall {
             actual: buffer.len(),
             required: size,
-        })
+        });
+    }
+
+    let (_, crc) = decode_raw(buffer, 4, step);
+    let crc: [u8; 4] = crc.try_into().map_err(|crc: Vec<u8>| Error::BufferTooSmall {
+        actual: crc.len(),
+        required: 4,
+    })?;
+    let expected = u32::from_be_bytes(crc);
+    let actual = crc32(&data);
+    if expected != actual {
+        return Err(Error::ChecksumMismatch { expected, actual });
+    }
+    Ok(data)
+}
+
+/// Compression method applied to a payload before it is embedded
+///
+/// Tried codecs are tagged with a single byte ahead of the (possibly compressed) payload so
+/// `decode_compressed` knows how to invert them.
+///
+/// Only available with the `std` feature: the underlying `flate2`/`zstd` codecs need it.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Codec {
+    /// The payload is embedded unmodified
+    Stored,
+    /// The payload is compressed with DEFLATE ([`flate2`]) before being embedded
+    Deflate,
+    /// The payload is compressed with zstd before being embedded
+    Zstd,
+}
+
+#[cfg(feature = "std")]
+impl Codec {
+    fn tag(self) -> u8 {
+        match self {
+            Codec::Stored => 0,
+            Codec::Deflate => 1,
+            Codec::Zstd => 2,
+        }
+    }
+
+    fn from_tag(tag: u8) -> Result<Self, Error> {
+        match tag {
+            0 => Ok(Codec::Stored),
+            1 => Ok(Codec::Deflate),
+            2 => Ok(Codec::Zstd),
+            _ => Err(Error::UnknownCodec { tag }),
+        }
+    }
+
+    fn compress(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            Codec::Stored => data.to_vec(),
+            Codec::Deflate => {
+                use std::io::Write;
+                let mut encoder =
+                    flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder
+                    .write_all(data)
+                    .expect("writing to an in-memory buffer cannot fail");
+                encoder
+                    .finish()
+                    .expect("writing to an in-memory buffer cannot fail")
+            }
+            Codec::Zstd => zstd::encode_all(data, 0).expect("compressing an in-memory buffer cannot fail"),
+        }
+    }
+
+    fn decompress(self, data: &[u8]) -> Result<Vec<u8>, Error> {
+        match self {
+            Codec::Stored => Ok(data.to_vec()),
+            Codec::Deflate => {
+                use std::io::Read;
+                let mut out = Vec::new();
+                flate2::read::DeflateDecoder::new(data)
+                    .read_to_end(&mut out)
+                    .map_err(|_| Error::Decompression)?;
+                Ok(out)
+            }
+            Codec::Zstd => zstd::decode_all(data).map_err(|_| Error::Decompression),
+        }
+    }
+}
+
+/// Encodes `data` into the `buffer` after compressing it with `codec`
+///
+/// Falls back to [`Codec::Stored`] when compression does not actually shrink `data`, so the
+/// embedded message never ends up larger than it would without compression.
+///
+/// Framed as a codec tag byte + `leb128(len) + payload`, with no magic marker or CRC-32
+/// trailer — unlike [`encode`]/[`decode`], this does not produce a verified container; see
+/// their doc comment.
+/// # Examples
+/// ```rust
+/// # use stegosaurus::{Error, byte::{encode_compressed, decode_compressed, Codec}};
+/// # fn main() -> Result<(), Error> {
+/// let msg = "Hello World".repeat(8);
+/// let mut buffer = vec![0; (2 + msg.len()) * 4];
+/// encode_compressed(&mut buffer, msg.as_bytes(), 2, Codec::Deflate)?;
+/// let decoded = decode_compressed(&buffer, 2)?;
+/// assert_eq!(msg.as_bytes(), decoded.as_slice());
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "std")]
+pub fn encode_compressed<'a>(
+    buffer: &'a mut [u8],
+    data: &[u8],
+    step: usize,
+    codec: Codec,
+) -> Result<&'a mut [u8], Error> {
+    debug_assert_step_size!(step);
+    let compressed = codec.compress(data);
+    let (codec, payload) = if compressed.len() < data.len() {
+        (codec, compressed)
     } else {
-        Ok(data)
+        (Codec::Stored, data.to_vec())
+    };
+
+    let mut header = vec![codec.tag()];
+    header.extend(leb128_encode(payload.len() as u64));
+    let buffer = encode_raw(buffer, &header, step)?;
+    encode_raw(buffer, &payload, step)
+}
+
+/// Decodes a payload previously written by [`encode_compressed`]
+#[cfg(feature = "std")]
+pub fn decode_compressed(buffer: &[u8], step: usize) -> Result<Vec<u8>, Error> {
+    debug_assert_step_size!(step);
+    let (buffer, tag) = decode_raw(buffer, 1, step);
+    let tag = *tag.first().ok_or(Error::BufferTooSmall {
+        actual: buffer.len(),
+        required: 1,
+    })?;
+    let codec = Codec::from_tag(tag)?;
+
+    let (buffer, size) = leb128_decode(buffer, step).ok_or(Error::BufferTooSmall {
+        actual: buffer.len(),
+        required: 1,
+    })?;
+    let size = size as usize;
+    let (_, payload) = decode_raw(buffer, size, step);
+    if payload.len() != size {
+        return Err(Error::BufferTooSmall {
+            actual: buffer.len(),
+            required: size,
+        });
+    }
+    codec.decompress(&payload)
+}
+
+/// Incrementally embeds a payload into cover bytes delivered in arbitrary-sized chunks
+///
+/// Useful when cover bytes arrive incrementally (e.g. row by row from a PNG decoder) and the
+/// whole image cannot be buffered up front. Feed cover bytes to [`Encoder::push`] as they
+/// become available, then call [`Encoder::finish`] to find out whether the payload fully fit.
+///
+/// Framed as a bare `leb128(len) + payload`, with no magic marker or CRC-32 trailer — unlike
+/// [`encode`]/[`decode`], this does not produce a verified container; see their doc comment.
+pub struct Encoder {
+    step: usize,
+    payload: Vec<u8>,
+    byte_index: usize,
+    bit_read: u8,
+}
+
+impl Encoder {
+    /// Creates an encoder that will embed `data` (prefixed with its LEB128 length) using the
+    /// `step` least significant bits of each cover byte
+    pub fn new(data: &[u8], step: usize) -> Self {
+        debug_assert_step_size!(step);
+        let mut payload = leb128_encode(data.len() as u64);
+        payload.extend_from_slice(data);
+        Self {
+            step,
+            payload,
+            byte_index: 0,
+            bit_read: 0,
+        }
+    }
+
+    /// Embeds as much of the payload as `chunk` has room for, in place
+    pub fn push(&mut self, chunk: &mut [u8]) {
+        for slot in chunk.iter_mut() {
+            if self.byte_index >= self.payload.len() {
+                break;
+            }
+            let byte_in = self.payload[self.byte_index];
+            for bit_write in 0..self.step {
+                let insert = (byte_in & (1 << self.bit_read)) >> self.bit_read;
+                let old = *slot & !(1 << bit_write);
+                *slot = old | (insert << bit_write);
+                self.bit_read += 1;
+            }
+            if self.bit_read as usize == u8::BITS as usize {
+                self.bit_read = 0;
+                self.byte_index += 1;
+            }
+        }
     }
+
+    /// Returns whether the whole payload has been embedded yet
+    pub fn is_finished(&self) -> bool {
+        self.byte_index >= self.payload.len()
+    }
+
+    /// Consumes the encoder, reporting whether the whole payload fit in the cover bytes it saw
+    pub fn finish(self) -> bool {
+        self.is_finished()
+    }
+}
+
+/// Incrementally recovers a payload from cover bytes delivered in arbitrary-sized chunks
+///
+/// Mirrors [`Encoder`] for streaming decode. Feed cover bytes to [`Decoder::push`] as they
+/// arrive; once the LEB128 length header and the full payload have been recovered, `push`
+/// returns [`Poll::Ready`] with the decoded bytes.
+pub struct Decoder {
+    reading_header: bool,
+    leb_value: u64,
+    leb_shift: u32,
+    leb_bytes: usize,
+    current: u8,
+    bit: u8,
+    payload_len: usize,
+    out: Vec<u8>,
+    step: usize,
+}
+
+impl Decoder {
+    /// Creates a decoder that reads cover bytes using the `step` least significant bits
+    pub fn new(step: usize) -> Self {
+        debug_assert_step_size!(step);
+        Self {
+            reading_header: true,
+            leb_value: 0,
+            leb_shift: 0,
+            leb_bytes: 0,
+            current: 0,
+            bit: 0,
+            payload_len: 0,
+            out: Vec::new(),
+            step,
+        }
+    }
+
+    /// Feeds the next chunk of cover bytes, returning the decoded payload once complete
+    pub fn push(&mut self, chunk: &[u8]) -> Poll<Vec<u8>> {
+        for slot in chunk {
+            for bit_read in 0..self.step {
+                self.current <<= 1;
+                self.current |= (*slot & (1 << bit_read)) >> bit_read;
+                self.bit += 1;
+                if self.bit != u8::BITS as u8 {
+                    continue;
+                }
+                let byte = self.current.reverse_bits();
+                self.current = 0;
+                self.bit = 0;
+                if self.reading_header {
+                    self.leb_value |= ((byte & 0x7f) as u64) << self.leb_shift;
+                    self.leb_shift += 7;
+                    self.leb_bytes += 1;
+                    if byte & 0x80 == 0 || self.leb_bytes >= LEB128_MAX_BYTES {
+                        self.reading_header = false;
+                        self.payload_len = self.leb_value as usize;
+                        self.out.reserve(self.payload_len);
+                        // A zero-length payload is already complete as soon as the header is:
+                        // the `out.len() == payload_len` check below never runs again otherwise,
+                        // since it only fires after pushing a payload byte.
+                        if self.payload_len == 0 {
+                            return Poll::Ready(core::mem::take(&mut self.out));
+                        }
+                    }
+                } else {
+                    self.out.push(byte);
+                    if self.out.len() == self.payload_len {
+                        return Poll::Ready(core::mem::take(&mut self.out));
+                    }
+                }
+            }
+        }
+        Poll::Pending
+    }
+}
+
+/// Reads `space` cover bytes out of `cover` one [`bytes::Buf`] chunk at a time, gathering a
+/// single decoded byte via [`gather_table`]
+#[cfg(feature = "bytes")]
+fn read_byte_buf<B: bytes::Buf>(cover: &mut B, step: usize, space: usize) -> Option<u8> {
+    if cover.remaining() < space {
+        return None;
+    }
+    let table = gather_table(step);
+    let mut current: u8 = 0;
+    let mut lane = 0;
+    while lane < space {
+        let chunk = cover.chunk();
+        let n = chunk.len().min(space - lane);
+        for (offset, slot) in chunk[..n].iter().enumerate() {
+            current |= table[*slot as usize][lane + offset];
+        }
+        cover.advance(n);
+        lane += n;
+    }
+    Some(current)
+}
+
+/// Encodes `data` directly into any [`bytes::BufMut`] sink, advancing `cover` by
+/// `bytes_per_byte(step)` bytes per payload byte
+///
+/// Framed as a bare `leb128(len) + payload`, with no magic marker or CRC-32 trailer — unlike
+/// [`encode`]/[`decode`], this does not produce a verified container; see their doc comment.
+///
+/// # Safety
+/// `cover` must already contain initialized cover bytes (e.g.
+/// a `&mut [u8]` over image data, or a `BytesMut` that has been resized/written to rather than
+/// merely `reserve`d) across the whole span this call will advance through: it only ever flips
+/// the low `step` bits of bytes already there, mirroring [`encode_raw_unsafe`], but
+/// `BufMut::chunk_mut` is allowed to hand back an `UninitSlice` over spare, never-written
+/// capacity. Reading that spare capacity to preserve its untouched high bits would be
+/// undefined behaviour, so the caller must guarantee `cover` has no such uninitialized region
+/// here. See [`decode_buf`] for the read-only counterpart, which has no such hazard and stays
+/// safe to call.
+#[cfg(feature = "bytes")]
+pub unsafe fn encode_buf<B: bytes::BufMut>(
+    cover: &mut B,
+    data: &[u8],
+    step: usize,
+) -> Result<(), Error> {
+    debug_assert_step_size!(step);
+    let space = bytes_per_byte(step);
+    let clear_mask = !(((1u16 << step) - 1) as u8);
+    let header = leb128_encode(data.len() as u64);
+    for byte_in in header.iter().chain(data.iter()) {
+        if cover.remaining_mut() < space {
+            return Err(Error::BufferTooSmall {
+                actual: cover.remaining_mut(),
+                required: space,
+            });
+        }
+        let lanes = &spread_table(step)[*byte_in as usize];
+        let mut written = 0;
+        while written < space {
+            let chunk = cover.chunk_mut();
+            let n = chunk.len().min(space - written);
+            // SAFETY: caller-guaranteed precondition (see function doc): `cover` already
+            // contains initialized cover bytes here.
+            let slots = unsafe { core::slice::from_raw_parts_mut(chunk.as_mut_ptr(), n) };
+            for (lane, slot) in slots.iter_mut().enumerate() {
+                *slot = (*slot & clear_mask) | lanes[written + lane];
+            }
+            // SAFETY: we just wrote exactly `n` bytes of `chunk` above.
+            unsafe { cover.advance_mut(n) };
+            written += n;
+        }
+    }
+    Ok(())
+}
+
+/// Decodes a payload written by [`encode_buf`] out of any [`bytes::Buf`] source
+#[cfg(feature = "bytes")]
+pub fn decode_buf<B: bytes::Buf>(cover: &mut B, step: usize) -> Result<bytes::Bytes, Error> {
+    use bytes::BufMut;
+
+    debug_assert_step_size!(step);
+    let space = bytes_per_byte(step);
+
+    let mut leb_value: u64 = 0;
+    let mut leb_shift = 0u32;
+    let mut leb_bytes = 0usize;
+    loop {
+        let byte = match read_byte_buf(cover, step, space) {
+            Some(byte) => byte,
+            None => {
+                return Err(Error::BufferTooSmall {
+                    actual: cover.remaining(),
+                    required: space,
+                })
+            }
+        };
+        leb_value |= ((byte & 0x7f) as u64) << leb_shift;
+        leb_shift += 7;
+        leb_bytes += 1;
+        if byte & 0x80 == 0 || leb_bytes >= LEB128_MAX_BYTES {
+            break;
+        }
+    }
+
+    let size = leb_value as usize;
+    let mut out = bytes::BytesMut::with_capacity(size);
+    for _ in 0..size {
+        let byte = match read_byte_buf(cover, step, space) {
+            Some(byte) => byte,
+            None => {
+                return Err(Error::BufferTooSmall {
+                    actual: cover.remaining(),
+                    required: space,
+                })
+            }
+        };
+        out.put_u8(byte);
+    }
+    Ok(out.freeze())
 }
 
+#[cfg(all(test, feature = "std"))]
 mod test {
 
     #[test]
     fn encode() {
         let msg = "Hi"; // 0b01001000 0b01101001
         let mut buffer = vec![0; 4];
-        unsafe { super::encode_raw_unsafe(&mut buffer, &msg.as_bytes(), 4) };
+        unsafe { super::encode_raw_unsafe(&mut buffer, msg.as_bytes(), 4) };
         assert_eq!(buffer, vec![0b1000, 0b0100, 0b1001, 0b0110]);
     }
 
@@ -184,8 +747,174 @@ mod test {
         const STEP: usize = 2;
         let msg = "Hello World";
         let mut buffer = vec![0; 88];
-        super::encode_raw(&mut buffer, &msg.as_bytes(), STEP).unwrap();
+        super::encode_raw(&mut buffer, msg.as_bytes(), STEP).unwrap();
         let decoded = super::decode_raw(&buffer, msg.len(), STEP);
         assert_eq!(msg.as_bytes(), decoded.1.as_slice());
     }
+
+    #[test]
+    fn leb128_short() {
+        assert_eq!(super::leb128_encode(0), vec![0x00]);
+        assert_eq!(super::leb128_encode(127), vec![0x7f]);
+    }
+
+    #[test]
+    fn leb128_multi_byte() {
+        assert_eq!(super::leb128_encode(300), vec![0xac, 0x02]);
+    }
+
+    #[test]
+    fn leb128_encode_decode() {
+        const STEP: usize = 2;
+        for value in [0u64, 1, 127, 128, 300, u64::MAX] {
+            let header = super::leb128_encode(value);
+            let mut buffer = vec![0; header.len() * super::bytes_per_byte(STEP)];
+            super::encode_raw(&mut buffer, &header, STEP).unwrap();
+            let (_, decoded) = super::leb128_decode(&buffer, STEP).unwrap();
+            assert_eq!(decoded, value);
+        }
+    }
+
+    #[test]
+    fn encode_decode_magic_and_checksum() {
+        const STEP: usize = 2;
+        let msg = "Hello World";
+        let mut buffer = vec![0; (4 + 1 + msg.len() + 4) * super::bytes_per_byte(STEP)];
+        super::encode(&mut buffer, msg.as_bytes(), STEP).unwrap();
+        let decoded = super::decode(&buffer, STEP).unwrap();
+        assert_eq!(msg.as_bytes(), decoded.as_slice());
+    }
+
+    #[test]
+    fn decode_no_payload() {
+        const STEP: usize = 2;
+        let buffer = vec![0; 40];
+        assert!(matches!(
+            super::decode(&buffer, STEP),
+            Err(crate::Error::NoPayload)
+        ));
+    }
+
+    #[test]
+    fn decode_no_payload_buffer_too_small_for_magic() {
+        const STEP: usize = 2;
+        // Too short to even hold the magic marker: must return an error, not panic.
+        let buffer = vec![0; 4];
+        assert!(matches!(
+            super::decode(&buffer, STEP),
+            Err(crate::Error::NoPayload)
+        ));
+    }
+
+    #[test]
+    fn decode_buffer_too_small_for_payload() {
+        const STEP: usize = 2;
+        let msg = "Hello World";
+        let mut buffer = vec![0; (4 + 1 + msg.len() + 4) * super::bytes_per_byte(STEP)];
+        super::encode(&mut buffer, msg.as_bytes(), STEP).unwrap();
+
+        // Truncate the cover buffer partway through the payload.
+        buffer.truncate((4 + 1 + 2) * super::bytes_per_byte(STEP));
+
+        assert!(matches!(
+            super::decode(&buffer, STEP),
+            Err(crate::Error::BufferTooSmall { .. })
+        ));
+    }
+
+    #[test]
+    fn decode_checksum_mismatch() {
+        const STEP: usize = 2;
+        let msg = "Hello World";
+        let mut buffer = vec![0; (4 + 1 + msg.len() + 4) * super::bytes_per_byte(STEP)];
+        super::encode(&mut buffer, msg.as_bytes(), STEP).unwrap();
+
+        // Flip a bit in the payload without touching the magic or length header
+        let space = super::bytes_per_byte(STEP);
+        buffer[(4 + 1) * space] ^= 1;
+
+        assert!(matches!(
+            super::decode(&buffer, STEP),
+            Err(crate::Error::ChecksumMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn encode_decode_compressed_deflate() {
+        const STEP: usize = 2;
+        let msg = "Hello World".repeat(8);
+        let mut buffer = vec![0; msg.len() * super::bytes_per_byte(STEP)];
+        super::encode_compressed(&mut buffer, msg.as_bytes(), STEP, super::Codec::Deflate).unwrap();
+        let decoded = super::decode_compressed(&buffer, STEP).unwrap();
+        assert_eq!(msg.as_bytes(), decoded.as_slice());
+    }
+
+    #[test]
+    fn encode_decode_compressed_stored_fallback() {
+        const STEP: usize = 2;
+        let msg = "x"; // too short to shrink under compression, should fall back to Stored
+        let mut buffer = vec![0; (2 + msg.len()) * super::bytes_per_byte(STEP)];
+        super::encode_compressed(&mut buffer, msg.as_bytes(), STEP, super::Codec::Deflate).unwrap();
+        let decoded = super::decode_compressed(&buffer, STEP).unwrap();
+        assert_eq!(msg.as_bytes(), decoded.as_slice());
+    }
+
+    #[test]
+    fn stream_encode_decode_chunked() {
+        use std::task::Poll;
+
+        const STEP: usize = 2;
+        let msg = "Hello World";
+        let mut buffer = vec![0; (1 + msg.len()) * super::bytes_per_byte(STEP)];
+
+        let mut encoder = super::Encoder::new(msg.as_bytes(), STEP);
+        for chunk in buffer.chunks_mut(3) {
+            encoder.push(chunk);
+        }
+        assert!(encoder.finish());
+
+        let mut decoder = super::Decoder::new(STEP);
+        let mut decoded = None;
+        for chunk in buffer.chunks(3) {
+            if let Poll::Ready(out) = decoder.push(chunk) {
+                decoded = Some(out);
+                break;
+            }
+        }
+        assert_eq!(msg.as_bytes(), decoded.unwrap().as_slice());
+    }
+
+    #[test]
+    fn stream_encode_decode_empty() {
+        use std::task::Poll;
+
+        const STEP: usize = 2;
+        let mut buffer = vec![0; super::bytes_per_byte(STEP)];
+
+        let mut encoder = super::Encoder::new(&[], STEP);
+        encoder.push(&mut buffer);
+        assert!(encoder.finish());
+
+        let mut decoder = super::Decoder::new(STEP);
+        match decoder.push(&buffer) {
+            Poll::Ready(out) => assert!(out.is_empty()),
+            Poll::Pending => panic!("a zero-length payload should complete as soon as the header does"),
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "bytes")]
+    fn encode_decode_buf() {
+        const STEP: usize = 2;
+        let msg = "Hello World";
+        let mut buffer = vec![0u8; (1 + msg.len()) * super::bytes_per_byte(STEP)];
+
+        let mut cover = buffer.as_mut_slice();
+        // SAFETY: `cover` is a slice over already-initialized cover bytes.
+        unsafe { super::encode_buf(&mut cover, msg.as_bytes(), STEP) }.unwrap();
+
+        let mut cover = buffer.as_slice();
+        let decoded = super::decode_buf(&mut cover, STEP).unwrap();
+        assert_eq!(msg.as_bytes(), decoded.as_ref());
+    }
 }
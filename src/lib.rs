@@ -1,7 +1,51 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 pub mod byte;
 
+#[cfg(feature = "std")]
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error("Buffer is too small: Buffer is {actual} bytes, but {required} bytes is required.")]
     BufferTooSmall { actual: usize, required: usize },
+    #[error("Unknown codec tag: {tag}")]
+    UnknownCodec { tag: u8 },
+    #[error("Failed to decompress payload")]
+    Decompression,
+    #[error("No embedded payload found")]
+    NoPayload,
+    #[error("Checksum mismatch: expected {expected:#010x}, got {actual:#010x}")]
+    ChecksumMismatch { expected: u32, actual: u32 },
+}
+
+/// Hand-written mirror of the `std`-enabled [`Error`], used when the `thiserror`-backed
+/// `derive(Error)` is unavailable under `no_std`
+#[cfg(not(feature = "std"))]
+#[derive(Debug)]
+pub enum Error {
+    BufferTooSmall { actual: usize, required: usize },
+    UnknownCodec { tag: u8 },
+    Decompression,
+    NoPayload,
+    ChecksumMismatch { expected: u32, actual: u32 },
+}
+
+#[cfg(not(feature = "std"))]
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::BufferTooSmall { actual, required } => write!(
+                f,
+                "Buffer is too small: Buffer is {actual} bytes, but {required} bytes is required."
+            ),
+            Error::UnknownCodec { tag } => write!(f, "Unknown codec tag: {tag}"),
+            Error::Decompression => write!(f, "Failed to decompress payload"),
+            Error::NoPayload => write!(f, "No embedded payload found"),
+            Error::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "Checksum mismatch: expected {expected:#010x}, got {actual:#010x}"
+            ),
+        }
+    }
 }